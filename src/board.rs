@@ -1,8 +1,15 @@
 //! Core logic
 
+use crate::dlx::Dlx;
+use crate::env::{Action, Environment, Observation, Step};
 use crate::utils::unsigned_diff;
 use rand::prelude::*;
 use std::cmp::Ordering;
+use std::time::{Duration, Instant};
+
+/// Number of `Environment` steps allowed per column before an episode is
+/// forced to end regardless of the remaining conflicts.
+const ENVIRONMENT_STEPS_PER_COLUMN_BUDGET: usize = 10;
 
 /// A squared board containing queens
 #[derive(Debug)]
@@ -11,6 +18,10 @@ pub struct Board {
     size: usize,
     /// Sorted vector of queens by row major order
     queens: Vec<Queen>,
+    /// Per-column row of the current `Environment` episode.
+    env_rows: Vec<usize>,
+    /// Number of `Environment` steps taken in the current episode.
+    env_steps: usize,
 }
 
 impl Board {
@@ -23,31 +34,46 @@ impl Board {
         Self {
             size,
             queens: Vec::new(),
+            env_rows: Vec::new(),
+            env_steps: 0,
         }
     }
 
-    /// Local search a configuration
+    /// Local search a configuration.
+    ///
+    /// The state is encoded as a permutation (`state[col]` is the row of
+    /// the queen on column `col`), which rules out row and column
+    /// conflicts by construction, alongside running occupancy counters for
+    /// both diagonal families. Neighbours are proposed by swapping two
+    /// columns' rows, so `objective` never has to be recomputed from
+    /// scratch: `delta_objective` derives the change from the counters in
+    /// O(1), making iterations orders of magnitude cheaper than the
+    /// O(N^2) free-placement encoding.
     #[must_use]
     pub fn simulated_annealing(
         &self,
         initial_temperature: f32,
         num_iterations: usize,
+        rng: &mut impl Rng,
     ) -> Vec<Queen> {
-        let mut rng = rand::thread_rng();
-        let mut state = self.random_state();
-        let mut e_current = self.objective(&state);
+        let size = self.size;
+        let (mut state, mut diag_count, mut anti_diag_count) = self.init_permutation_state(rng);
+
+        let mut e_current = conflict_objective(&diag_count, &anti_diag_count);
 
         let mut t = initial_temperature;
         let mut i = 0;
         while i < num_iterations && e_current < 0. {
-            let neighbour_state = self.random_neighbour(&state);
-
-            let e_next = self.objective(&neighbour_state);
-            if e_current > e_next {
-                state = neighbour_state;
-                e_current = e_next;
-            } else if acceptance_probability(e_current, e_next, t) >= rng.gen::<f32>() {
-                state = neighbour_state;
+            let (a, b) = self.random_neighbour(rng);
+            let delta = delta_objective(&diag_count, &anti_diag_count, size, &state, a, b);
+            let e_next = e_current + delta;
+
+            if delta >= 0. || acceptance_probability(e_current, e_next, t) >= rng.gen::<f32>() {
+                remove_queen(&mut diag_count, &mut anti_diag_count, a, state[a], size);
+                remove_queen(&mut diag_count, &mut anti_diag_count, b, state[b], size);
+                state.swap(a, b);
+                place_queen(&mut diag_count, &mut anti_diag_count, a, state[a], size);
+                place_queen(&mut diag_count, &mut anti_diag_count, b, state[b], size);
                 e_current = e_next;
             }
 
@@ -55,90 +81,262 @@ impl Board {
             i += 1;
         }
 
-        return state;
+        permutation_to_queens(&state)
     }
 
-    /// Places `self.size` queens randomly on the board
-    fn random_state(&self) -> Vec<Queen> {
-        let mut rng = rand::thread_rng();
-        let mut state: Vec<Queen> = Vec::with_capacity(self.size);
+    /// Local search a configuration against a wall-clock deadline instead of
+    /// a fixed iteration count, for callers that need a predictable running
+    /// time rather than a predictable amount of work.
+    ///
+    /// Temperature cools geometrically (`t *= alpha` every iteration, so
+    /// `alpha` should be just under `1.0`) instead of `simulated_annealing`'s
+    /// `1/i` schedule. Whenever `REHEAT_PATIENCE_STEPS` consecutive
+    /// iterations fail to improve on the best state found so far, the
+    /// temperature is reheated back to `initial_temperature` to kick the
+    /// search out of a local optimum, and the search keeps running until a
+    /// conflict-free state is found or `deadline` elapses. The best state
+    /// seen across every reheat is tracked separately from the (possibly worse, accepted-for-exploration)
+    /// current state and is what gets returned.
+    #[must_use]
+    pub fn simulated_annealing_timed(
+        &self,
+        initial_temperature: f32,
+        alpha: f32,
+        deadline: Duration,
+        rng: &mut impl Rng,
+    ) -> Vec<Queen> {
+        const REHEAT_PATIENCE_STEPS: usize = 1000;
 
-        for _ in 0..self.size {
-            self.insert_new(Queen::random(&mut rng, self.size), &mut state);
-        }
+        let size = self.size;
+        let (mut state, mut diag_count, mut anti_diag_count) = self.init_permutation_state(rng);
 
-        state
-    }
+        let mut e_current = conflict_objective(&diag_count, &anti_diag_count);
+        let mut best_state = state.clone();
+        let mut best_e = e_current;
 
-    fn insert_new(&self, mut queen: Queen, state: &mut Vec<Queen>) -> usize {
-        // We look for the insertion index because we want to maintain our
-        // vector sorted
-        // Note: We could use binary search here
-        let mut i = 0;
-        while i < state.len() && queen > state[i] {
-            i += 1;
-        }
+        let mut t = initial_temperature;
+        let mut stalled_steps = 0;
+        let start = Instant::now();
+        while best_e < 0. && start.elapsed() < deadline {
+            let (a, b) = self.random_neighbour(rng);
+            let delta = delta_objective(&diag_count, &anti_diag_count, size, &state, a, b);
+            let e_next = e_current + delta;
+
+            if delta >= 0. || acceptance_probability(e_current, e_next, t) >= rng.gen::<f32>() {
+                remove_queen(&mut diag_count, &mut anti_diag_count, a, state[a], size);
+                remove_queen(&mut diag_count, &mut anti_diag_count, b, state[b], size);
+                state.swap(a, b);
+                place_queen(&mut diag_count, &mut anti_diag_count, a, state[a], size);
+                place_queen(&mut diag_count, &mut anti_diag_count, b, state[b], size);
+                e_current = e_next;
+            }
 
-        // We check if the queen already exists if not we can insert it and
-        // continue
-        if i == state.len() || queen != state[i] {
-            state.insert(i, queen);
-            return i;
-        }
+            if e_current > best_e {
+                best_e = e_current;
+                best_state = state.clone();
+                stalled_steps = 0;
+            } else {
+                stalled_steps += 1;
+                if stalled_steps >= REHEAT_PATIENCE_STEPS {
+                    t = initial_temperature;
+                    stalled_steps = 0;
+                }
+            }
 
-        let first_index = i;
+            t *= alpha;
+        }
 
-        debug_assert_eq!(queen, state[i]);
+        permutation_to_queens(&best_state)
+    }
 
-        // We increment the queen position index and try to find one
-        // that does not exists
-        while i < state.len() - 1 && queen == state[i] {
-            queen.increment_position_index(1, self.size);
-            i += 1;
+    /// Builds a fresh random permutation state for `simulated_annealing`'s
+    /// family of methods, along with its diagonal occupancy counters.
+    fn init_permutation_state(&self, rng: &mut impl Rng) -> (Vec<usize>, Vec<usize>, Vec<usize>) {
+        let size = self.size;
+        let state = self.random_state(rng);
+        let mut diag_count = vec![0usize; 2 * size - 1];
+        let mut anti_diag_count = vec![0usize; 2 * size - 1];
+        for (col, &row) in state.iter().enumerate() {
+            place_queen(&mut diag_count, &mut anti_diag_count, col, row, size);
         }
 
-        if i < state.len() - 1 || queen != state[i] {
-            state.insert(i, queen);
-            return i;
-        } else if queen.get_position_index(self.size) < self.size * self.size - 1 {
-            queen.increment_position_index(1, self.size);
-            state.push(queen);
-            return i;
-        }
+        (state, diag_count, anti_diag_count)
+    }
 
-        debug_assert_eq!(
-            queen.get_position_index(self.size),
-            self.size * self.size - 1
-        );
+    /// Places `self.size` queens on the board as a random permutation, one
+    /// per column, so no two ever share a row or column.
+    fn random_state(&self, rng: &mut impl Rng) -> Vec<usize> {
+        let mut state: Vec<usize> = (0..self.size).collect();
+        state.shuffle(rng);
+        state
+    }
 
-        // We have reached the end now we have to check the beginning
-        queen = Queen::new(0, 0);
-        i = 0;
-        while i < first_index && queen == state[i] {
-            queen.increment_position_index(1, self.size);
-            i += 1;
+    /// Picks two distinct columns to swap the rows of, proposing a
+    /// neighbour that keeps the permutation a bijection.
+    #[must_use]
+    fn random_neighbour(&self, rng: &mut impl Rng) -> (usize, usize) {
+        let i = rng.gen_range(0..self.size);
+        let j = loop {
+            let j = rng.gen_range(0..self.size);
+            if j != i {
+                break j;
+            }
+        };
+
+        (i, j)
+    }
+
+    /// Enumerates every valid placement of `self.size` non-attacking queens
+    /// using Knuth's Algorithm X with dancing links, proving completeness
+    /// (and absence of a solution) rather than relying on heuristics.
+    ///
+    /// The problem is modelled as an exact cover: the `size` files and
+    /// `size` ranks are primary constraints that must each hold exactly one
+    /// queen, while the `2 * size - 1` "/" diagonals (`x + y`) and
+    /// `2 * size - 1` "\" diagonals (`x - y + size - 1`) are secondary
+    /// constraints that may be covered at most once.
+    #[must_use]
+    pub fn solve_exact(&self) -> impl Iterator<Item = Vec<Queen>> {
+        let size = self.size;
+        let num_diagonals = 2 * size - 1;
+
+        // Primary: `size` files then `size` ranks.
+        // Secondary: `num_diagonals` "/" diagonals then `num_diagonals` "\" diagonals.
+        let mut dlx = Dlx::new(2 * size, 2 * num_diagonals);
+
+        let mut cells = Vec::with_capacity(size * size);
+        for y in 0..size {
+            for x in 0..size {
+                let file = x;
+                let rank = size + y;
+                let diagonal = 2 * size + (x + y);
+                let anti_diagonal = 2 * size + num_diagonals + (x + size - 1 - y);
+
+                dlx.add_row(cells.len(), &[file, rank, diagonal, anti_diagonal]);
+                cells.push((x, y));
+            }
         }
 
-        state.insert(i, queen);
-        return i;
+        dlx.solve().into_iter().map(move |solution| {
+            let mut queens: Vec<Queen> = solution
+                .into_iter()
+                .map(|r| {
+                    let (x, y) = cells[r];
+                    Queen::new(x, y)
+                })
+                .collect();
+            queens.sort();
+            queens
+        })
     }
 
-    /// Generates a new random neighbour of the current configuration
-    /// which is the same configuration but one queen moved
+    /// Finds a configuration with few endangered queens using the
+    /// min-conflicts local search heuristic, which scales to thousands of
+    /// queens where recomputing the O(N^2) `objective` on every move stalls.
+    ///
+    /// The board is encoded as one queen per column (`state[col]` is its
+    /// row) alongside running occupancy counters for rows and both
+    /// diagonal families, so evaluating and applying a move costs O(N)
+    /// instead of O(N^2). Whenever `PLATEAU_STEPS` consecutive steps fail
+    /// to reduce the number of conflicted columns, the search restarts
+    /// from a fresh random state to escape the plateau.
     #[must_use]
-    fn random_neighbour(&self, state: &Vec<Queen>) -> Vec<Queen> {
-        let mut rng = rand::thread_rng();
-        let mut neighbour_state = state.clone();
+    pub fn min_conflicts(&self, max_steps: usize, rng: &mut impl Rng) -> Vec<Queen> {
+        const PLATEAU_STEPS: usize = 100;
+
+        let size = self.size;
+        let num_diagonals = 2 * size - 1;
+
+        let mut state = vec![0usize; size];
+        let mut row_count = vec![0usize; size];
+        let mut diag_count = vec![0usize; num_diagonals];
+        let mut anti_diag_count = vec![0usize; num_diagonals];
+        randomize_min_conflicts_state(
+            &mut state,
+            &mut row_count,
+            &mut diag_count,
+            &mut anti_diag_count,
+            size,
+            rng,
+        );
+
+        let mut best_conflicted =
+            count_conflicted_columns(&state, &row_count, &diag_count, &anti_diag_count, size);
+        let mut stalled_steps = 0;
+
+        let mut step = 0;
+        while step < max_steps && best_conflicted > 0 {
+            let conflicted: Vec<usize> = (0..size)
+                .filter(|&col| {
+                    is_conflicted(&state, &row_count, &diag_count, &anti_diag_count, size, col)
+                })
+                .collect();
+
+            let col = conflicted[rng.gen_range(0..conflicted.len())];
+            let row = state[col];
+
+            row_count[row] -= 1;
+            diag_count[col + row] -= 1;
+            anti_diag_count[col + size - 1 - row] -= 1;
+
+            let mut best_row = row;
+            let mut best_row_conflicts = usize::MAX;
+            let mut num_best = 0usize;
+            for r in 0..size {
+                let conflicts =
+                    row_count[r] + diag_count[col + r] + anti_diag_count[col + size - 1 - r];
+                match conflicts.cmp(&best_row_conflicts) {
+                    Ordering::Less => {
+                        best_row_conflicts = conflicts;
+                        best_row = r;
+                        num_best = 1;
+                    }
+                    Ordering::Equal => {
+                        num_best += 1;
+                        if rng.gen_range(0..num_best) == 0 {
+                            best_row = r;
+                        }
+                    }
+                    Ordering::Greater => {}
+                }
+            }
 
-        let new_queen = self.insert_new(Queen::random(&mut rng, self.size), &mut neighbour_state);
+            state[col] = best_row;
+            row_count[best_row] += 1;
+            diag_count[col + best_row] += 1;
+            anti_diag_count[col + size - 1 - best_row] += 1;
+
+            let conflicted_count =
+                count_conflicted_columns(&state, &row_count, &diag_count, &anti_diag_count, size);
+            if conflicted_count < best_conflicted {
+                best_conflicted = conflicted_count;
+                stalled_steps = 0;
+            } else {
+                stalled_steps += 1;
+                if stalled_steps >= PLATEAU_STEPS {
+                    randomize_min_conflicts_state(
+                        &mut state,
+                        &mut row_count,
+                        &mut diag_count,
+                        &mut anti_diag_count,
+                        size,
+                        rng,
+                    );
+                    best_conflicted = count_conflicted_columns(
+                        &state,
+                        &row_count,
+                        &diag_count,
+                        &anti_diag_count,
+                        size,
+                    );
+                    stalled_steps = 0;
+                }
+            }
 
-        let n = match rng.gen::<usize>() % neighbour_state.len() {
-            n if n == new_queen => (n + 1) % neighbour_state.len(),
-            n => n,
-        };
-        neighbour_state.remove(n);
+            step += 1;
+        }
 
-        neighbour_state
+        permutation_to_queens(&state)
     }
 
     /// Computes number of pairs of endangered queens
@@ -174,6 +372,54 @@ impl Board {
     }
 }
 
+impl Environment for Board {
+    /// Places `self.size` queens at random, one per column, and starts a
+    /// fresh episode.
+    fn reset(&mut self) -> Observation {
+        let mut rng = rand::thread_rng();
+
+        self.env_steps = 0;
+        self.env_rows = (0..self.size)
+            .map(|_| rng.gen_range(0..self.size))
+            .collect();
+
+        Observation {
+            rows: self.env_rows.clone(),
+            num_conflicts: self.env_conflicts(),
+        }
+    }
+
+    /// Moves the queen on `action.column` to `action.row`.
+    fn step(&mut self, action: Action) -> Step {
+        let conflicts_before = self.env_conflicts();
+
+        self.env_rows[action.column] = action.row;
+        self.env_steps += 1;
+
+        let conflicts_after = self.env_conflicts();
+        let done = conflicts_after == 0
+            || self.env_steps >= self.size * ENVIRONMENT_STEPS_PER_COLUMN_BUDGET;
+
+        Step {
+            observation: Observation {
+                rows: self.env_rows.clone(),
+                num_conflicts: conflicts_after,
+            },
+            reward: conflicts_before as f32 - conflicts_after as f32,
+            done,
+        }
+    }
+}
+
+impl Board {
+    /// Number of pairs of queens currently endangering each other in the
+    /// `Environment` episode state, reusing `objective`.
+    #[must_use]
+    fn env_conflicts(&self) -> usize {
+        (-self.objective(&permutation_to_queens(&self.env_rows))) as usize
+    }
+}
+
 /// A structure encapsulating the position of the queen
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Queen {
@@ -189,15 +435,6 @@ impl Queen {
         Self { x, y }
     }
 
-    /// Creates a queen with random position in a square of the given size
-    #[must_use]
-    pub fn random(rng: &mut ThreadRng, size: usize) -> Self {
-        Self {
-            x: rng.gen::<usize>() % size,
-            y: rng.gen::<usize>() % size,
-        }
-    }
-
     /// Increments the row major order of the queen and updates its position
     #[inline]
     pub fn increment_position_index(&mut self, increment: usize, size: usize) {
@@ -241,6 +478,109 @@ impl Ord for Queen {
     }
 }
 
+/// Converts a permutation state (`state[col]` is the row of the queen on
+/// column `col`) into a row-major sorted `Vec<Queen>`.
+fn permutation_to_queens(state: &[usize]) -> Vec<Queen> {
+    let mut queens: Vec<Queen> = state
+        .iter()
+        .enumerate()
+        .map(|(col, &row)| Queen::new(col, row))
+        .collect();
+    queens.sort();
+    queens
+}
+
+/// Adds a queen's diagonal occupancy to the `simulated_annealing` counters.
+#[inline]
+fn place_queen(
+    diag_count: &mut [usize],
+    anti_diag_count: &mut [usize],
+    col: usize,
+    row: usize,
+    size: usize,
+) {
+    diag_count[col + row] += 1;
+    anti_diag_count[col + size - 1 - row] += 1;
+}
+
+/// Removes a queen's diagonal occupancy from the `simulated_annealing`
+/// counters.
+#[inline]
+fn remove_queen(
+    diag_count: &mut [usize],
+    anti_diag_count: &mut [usize],
+    col: usize,
+    row: usize,
+    size: usize,
+) {
+    diag_count[col + row] -= 1;
+    anti_diag_count[col + size - 1 - row] -= 1;
+}
+
+/// Computes the number of endangered pairs of queens, negated to match
+/// `Board::objective`'s sign convention, from the diagonal occupancy
+/// counters alone. Row and column conflicts are impossible because the
+/// state is a permutation.
+#[must_use]
+fn conflict_objective(diag_count: &[usize], anti_diag_count: &[usize]) -> f32 {
+    let pairs_on = |counts: &[usize]| -> usize { counts.iter().map(|&n| n * (n.saturating_sub(1)) / 2).sum() };
+
+    -((pairs_on(diag_count) + pairs_on(anti_diag_count)) as f32)
+}
+
+/// Computes the change in `conflict_objective` from swapping the queens in
+/// columns `i` and `j`, by inspecting only the diagonals the two queens
+/// leave and enter.
+///
+/// The counters don't yet reflect the swap, so a diagonal the two queens
+/// would come to share with *each other* (rather than with some other
+/// queen) isn't reflected by a lookup and has to be corrected for
+/// explicitly, in both directions.
+#[must_use]
+fn delta_objective(
+    diag_count: &[usize],
+    anti_diag_count: &[usize],
+    size: usize,
+    state: &[usize],
+    i: usize,
+    j: usize,
+) -> f32 {
+    let (row_i, row_j) = (state[i], state[j]);
+
+    let old_diag_i = i + row_i;
+    let old_anti_i = i + size - 1 - row_i;
+    let old_diag_j = j + row_j;
+    let old_anti_j = j + size - 1 - row_j;
+    let new_diag_i = i + row_j;
+    let new_anti_i = i + size - 1 - row_j;
+    let new_diag_j = j + row_i;
+    let new_anti_j = j + size - 1 - row_i;
+
+    let mut lost = (diag_count[old_diag_i] - 1)
+        + (anti_diag_count[old_anti_i] - 1)
+        + (diag_count[old_diag_j] - 1)
+        + (anti_diag_count[old_anti_j] - 1);
+    if old_diag_i == old_diag_j {
+        lost -= 1;
+    }
+    if old_anti_i == old_anti_j {
+        lost -= 1;
+    }
+
+    let mut gained = diag_count[new_diag_i]
+        + anti_diag_count[new_anti_i]
+        + diag_count[new_diag_j]
+        + anti_diag_count[new_anti_j];
+    if new_diag_i == new_diag_j {
+        gained += 1;
+    }
+    if new_anti_i == new_anti_j {
+        gained += 1;
+    }
+
+    lost as f32 - gained as f32
+}
+
 /// Computes the acceptance probability of the next state which have less
 /// energy than the current one.
 #[inline]
@@ -251,10 +591,89 @@ fn acceptance_probability(energy: f32, energy_next: f32, temperature: f32) -> f3
     f32::exp((energy_next - energy) / temperature)
 }
 
+/// Reinitializes a `min_conflicts` permutation state with a fresh random row
+/// per column, rebuilding the row and diagonal occupancy counters from
+/// scratch.
+fn randomize_min_conflicts_state(
+    state: &mut [usize],
+    row_count: &mut [usize],
+    diag_count: &mut [usize],
+    anti_diag_count: &mut [usize],
+    size: usize,
+    rng: &mut impl Rng,
+) {
+    row_count.iter_mut().for_each(|c| *c = 0);
+    diag_count.iter_mut().for_each(|c| *c = 0);
+    anti_diag_count.iter_mut().for_each(|c| *c = 0);
+
+    for (col, row) in state.iter_mut().enumerate() {
+        *row = rng.gen_range(0..size);
+        row_count[*row] += 1;
+        diag_count[col + *row] += 1;
+        anti_diag_count[col + size - 1 - *row] += 1;
+    }
+}
+
+/// Checks whether the queen in `col` shares a row or diagonal with another
+/// queen, using the `min_conflicts` occupancy counters.
+#[inline]
+#[must_use]
+fn is_conflicted(
+    state: &[usize],
+    row_count: &[usize],
+    diag_count: &[usize],
+    anti_diag_count: &[usize],
+    size: usize,
+    col: usize,
+) -> bool {
+    let row = state[col];
+    row_count[row] > 1 || diag_count[col + row] > 1 || anti_diag_count[col + size - 1 - row] > 1
+}
+
+/// Counts how many columns currently hold a queen in conflict, using the
+/// `min_conflicts` occupancy counters.
+#[must_use]
+fn count_conflicted_columns(
+    state: &[usize],
+    row_count: &[usize],
+    diag_count: &[usize],
+    anti_diag_count: &[usize],
+    size: usize,
+) -> usize {
+    (0..size)
+        .filter(|&col| is_conflicted(state, row_count, diag_count, anti_diag_count, size, col))
+        .count()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn solve_exact_finds_every_solution() {
+        let b = Board::new(4);
+        let mut solutions: Vec<Vec<Queen>> = b.solve_exact().collect();
+        solutions.sort();
+
+        assert_eq!(
+            solutions,
+            vec![
+                vec![
+                    Queen::new(1, 0),
+                    Queen::new(3, 1),
+                    Queen::new(0, 2),
+                    Queen::new(2, 3),
+                ],
+                vec![
+                    Queen::new(2, 0),
+                    Queen::new(0, 1),
+                    Queen::new(3, 2),
+                    Queen::new(1, 3),
+                ],
+            ]
+        );
+    }
+
     #[test]
     fn objective() {
         let b = Board::new(4);
@@ -282,32 +701,117 @@ mod tests {
     }
 
     #[test]
-    fn random_state() {
+    fn random_state_is_a_permutation() {
+        let b = Board::new(8);
+        let mut rng = rand::rngs::StdRng::seed_from_u64(0);
+        let mut state = b.random_state(&mut rng);
+
+        state.sort_unstable();
+        assert_eq!(state, (0..8).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn random_neighbour_picks_two_distinct_columns() {
         let b = Board::new(4);
-        let state = b.random_state();
+        let mut rng = rand::rngs::StdRng::seed_from_u64(0);
+        let (i, j) = b.random_neighbour(&mut rng);
 
-        for i in 1..state.len() {
-            println!("{:?} {:?}", state[i - 1], state[i]);
-            assert!(state[i - 1] < state[i]);
+        assert_ne!(i, j);
+        assert!(i < 4 && j < 4);
+    }
+
+    #[test]
+    fn delta_objective_matches_a_freshly_computed_objective() {
+        let b = Board::new(6);
+        let mut rng = rand::rngs::StdRng::seed_from_u64(7);
+        let size = 6;
+
+        let state = b.random_state(&mut rng);
+        let mut diag_count = vec![0usize; 2 * size - 1];
+        let mut anti_diag_count = vec![0usize; 2 * size - 1];
+        for (col, &row) in state.iter().enumerate() {
+            place_queen(&mut diag_count, &mut anti_diag_count, col, row, size);
         }
+
+        let (i, j) = b.random_neighbour(&mut rng);
+        let before = conflict_objective(&diag_count, &anti_diag_count);
+        let delta = delta_objective(&diag_count, &anti_diag_count, size, &state, i, j);
+
+        let mut swapped = state.clone();
+        swapped.swap(i, j);
+        let after = b.objective(&permutation_to_queens(&swapped));
+
+        assert_eq!(before + delta, after);
     }
 
     #[test]
-    fn random_neighbour() {
+    fn simulated_annealing_is_reproducible_for_a_given_seed() {
         let b = Board::new(4);
-        let state = vec![
-            Queen::new(1, 0),
-            Queen::new(3, 0),
-            Queen::new(0, 2),
-            Queen::new(2, 3),
-        ];
-        let neighbour = b.random_neighbour(&state);
 
-        assert_ne!(state, neighbour);
+        let mut rng_a = rand::rngs::StdRng::seed_from_u64(42);
+        let solution_a = b.simulated_annealing(1000., 10_000, &mut rng_a);
 
-        for i in 1..neighbour.len() {
-            println!("{:?} {:?}", state[i - 1], state[i]);
-            assert!(neighbour[i - 1] < neighbour[i]);
-        }
+        let mut rng_b = rand::rngs::StdRng::seed_from_u64(42);
+        let solution_b = b.simulated_annealing(1000., 10_000, &mut rng_b);
+
+        assert_eq!(solution_a, solution_b);
+    }
+
+    #[test]
+    fn min_conflicts_finds_a_solution() {
+        let b = Board::new(8);
+        let mut rng = rand::rngs::StdRng::seed_from_u64(0);
+        let state = b.min_conflicts(10_000, &mut rng);
+
+        assert_eq!(b.objective(&state), 0f32);
+    }
+
+    #[test]
+    fn environment_reset_reports_a_queen_per_column() {
+        let mut b = Board::new(4);
+        let observation = b.reset();
+
+        assert_eq!(observation.rows.len(), 4);
+        assert_eq!(observation.num_conflicts, b.env_conflicts());
+    }
+
+    #[test]
+    fn environment_step_rewards_a_conflict_reduction() {
+        let mut b = Board::new(4);
+        b.reset();
+        b.env_rows = vec![0, 0, 3, 1];
+
+        let step = b.step(Action { column: 0, row: 2 });
+
+        assert_eq!(step.observation.rows, vec![2, 0, 3, 1]);
+        assert_eq!(step.observation.num_conflicts, 0);
+        assert_eq!(step.reward, 1.);
+        assert!(step.done);
+    }
+
+    #[test]
+    fn simulated_annealing_timed_is_reproducible_for_a_given_seed() {
+        let b = Board::new(4);
+        let deadline = Duration::from_millis(50);
+
+        let mut rng_a = rand::rngs::StdRng::seed_from_u64(42);
+        let solution_a = b.simulated_annealing_timed(1000., 0.999, deadline, &mut rng_a);
+
+        let mut rng_b = rand::rngs::StdRng::seed_from_u64(42);
+        let solution_b = b.simulated_annealing_timed(1000., 0.999, deadline, &mut rng_b);
+
+        assert_eq!(solution_a, solution_b);
+    }
+
+    #[test]
+    fn simulated_annealing_timed_respects_its_deadline() {
+        let b = Board::new(8);
+        let deadline = Duration::from_millis(100);
+        let mut rng = rand::rngs::StdRng::seed_from_u64(0);
+
+        let start = Instant::now();
+        let _ = b.simulated_annealing_timed(1000., 0.999, deadline, &mut rng);
+
+        assert!(start.elapsed() < deadline * 10);
     }
 }
@@ -0,0 +1,229 @@
+//! A minimal, array-based implementation of Knuth's Algorithm X using
+//! dancing links, supporting secondary (optional) columns that must be
+//! covered at most once but are never chosen as a branching column.
+
+/// Index of the root node, always `0`.
+const ROOT: usize = 0;
+
+/// A toroidal doubly linked matrix used to solve exact cover problems.
+pub struct Dlx {
+    left: Vec<usize>,
+    right: Vec<usize>,
+    up: Vec<usize>,
+    down: Vec<usize>,
+    /// Column header index for every node; for header nodes this is their own index.
+    column: Vec<usize>,
+    /// Number of rows currently linked under each column header.
+    size: Vec<usize>,
+    /// Original row index a node belongs to, `None` for header nodes.
+    row: Vec<Option<usize>>,
+}
+
+impl Dlx {
+    /// Creates an empty matrix with `num_primary` primary columns, which
+    /// must be covered exactly once, followed by `num_secondary` secondary
+    /// columns, which are covered at most once and never picked as a branch
+    /// column.
+    #[must_use]
+    pub fn new(num_primary: usize, num_secondary: usize) -> Self {
+        let num_columns = num_primary + num_secondary;
+        let mut dlx = Self {
+            left: vec![ROOT],
+            right: vec![ROOT],
+            up: vec![ROOT],
+            down: vec![ROOT],
+            column: vec![ROOT],
+            size: vec![0],
+            row: vec![None],
+        };
+
+        for c in 1..=num_columns {
+            dlx.up.push(c);
+            dlx.down.push(c);
+            dlx.column.push(c);
+            dlx.size.push(0);
+            dlx.row.push(None);
+
+            if c <= num_primary {
+                // Primary columns are linked into the circular header row so
+                // that `choose_column` can find them.
+                let last = dlx.left[ROOT];
+                dlx.left.push(last);
+                dlx.right.push(ROOT);
+                dlx.right[last] = c;
+                dlx.left[ROOT] = c;
+            } else {
+                // Secondary columns are never linked into the header row.
+                dlx.left.push(c);
+                dlx.right.push(c);
+            }
+        }
+
+        dlx
+    }
+
+    /// Adds a row covering the given columns (0-indexed).
+    pub fn add_row(&mut self, row: usize, columns: &[usize]) {
+        let start = self.left.len();
+
+        for (i, &col) in columns.iter().enumerate() {
+            let header = col + 1;
+            let node = self.left.len();
+
+            let up = self.up[header];
+            self.up.push(up);
+            self.down.push(header);
+            self.down[up] = node;
+            self.up[header] = node;
+
+            self.column.push(header);
+            self.row.push(Some(row));
+            self.size[header] += 1;
+
+            if i == 0 {
+                self.left.push(node);
+                self.right.push(node);
+            } else {
+                let prev = node - 1;
+                self.left.push(prev);
+                self.right.push(start);
+                self.right[prev] = node;
+                self.left[start] = node;
+            }
+        }
+    }
+
+    /// Enumerates every exact cover of the matrix, returning, for each
+    /// solution, the set of row indices that together cover every primary
+    /// column exactly once (and every touched secondary column at most
+    /// once).
+    #[must_use]
+    pub fn solve(&mut self) -> Vec<Vec<usize>> {
+        let mut solutions = Vec::new();
+        let mut partial = Vec::new();
+        self.search(&mut partial, &mut solutions);
+        solutions
+    }
+
+    fn search(&mut self, partial: &mut Vec<usize>, solutions: &mut Vec<Vec<usize>>) {
+        let c = match self.choose_column() {
+            Some(c) => c,
+            None => {
+                solutions.push(partial.clone());
+                return;
+            }
+        };
+
+        self.cover(c);
+
+        let mut r = self.down[c];
+        while r != c {
+            partial.push(self.row[r].expect("a matrix node always belongs to a row"));
+
+            let mut j = self.right[r];
+            while j != r {
+                self.cover(self.column[j]);
+                j = self.right[j];
+            }
+
+            self.search(partial, solutions);
+            partial.pop();
+
+            let mut j = self.left[r];
+            while j != r {
+                self.uncover(self.column[j]);
+                j = self.left[j];
+            }
+
+            r = self.down[r];
+        }
+
+        self.uncover(c);
+    }
+
+    /// Picks the uncovered primary column with the fewest remaining rows
+    /// (the S-heuristic), to minimize branching.
+    fn choose_column(&self) -> Option<usize> {
+        if self.right[ROOT] == ROOT {
+            return None;
+        }
+
+        let mut best = self.right[ROOT];
+        let mut c = self.right[best];
+        while c != ROOT {
+            if self.size[c] < self.size[best] {
+                best = c;
+            }
+            c = self.right[c];
+        }
+
+        Some(best)
+    }
+
+    fn cover(&mut self, c: usize) {
+        self.right[self.left[c]] = self.right[c];
+        self.left[self.right[c]] = self.left[c];
+
+        let mut i = self.down[c];
+        while i != c {
+            let mut j = self.right[i];
+            while j != i {
+                self.down[self.up[j]] = self.down[j];
+                self.up[self.down[j]] = self.up[j];
+                self.size[self.column[j]] -= 1;
+                j = self.right[j];
+            }
+            i = self.down[i];
+        }
+    }
+
+    fn uncover(&mut self, c: usize) {
+        let mut i = self.up[c];
+        while i != c {
+            let mut j = self.left[i];
+            while j != i {
+                self.size[self.column[j]] += 1;
+                self.down[self.up[j]] = j;
+                self.up[self.down[j]] = j;
+                j = self.left[j];
+            }
+            i = self.up[i];
+        }
+
+        self.right[self.left[c]] = c;
+        self.left[self.right[c]] = c;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A trivial exact cover: two disjoint rows exactly tile two columns.
+    #[test]
+    fn solves_trivial_cover() {
+        let mut dlx = Dlx::new(2, 0);
+        dlx.add_row(0, &[0]);
+        dlx.add_row(1, &[1]);
+        dlx.add_row(2, &[0, 1]);
+
+        let mut solutions = dlx.solve();
+        for solution in &mut solutions {
+            solution.sort_unstable();
+        }
+        solutions.sort();
+
+        assert_eq!(solutions, vec![vec![0, 1], vec![2]]);
+    }
+
+    #[test]
+    fn secondary_columns_are_never_branched_on() {
+        // Column 1 is secondary and is left uncovered by every row; the
+        // solver must still find the cover of the single primary column.
+        let mut dlx = Dlx::new(1, 1);
+        dlx.add_row(0, &[0]);
+
+        let solutions = dlx.solve();
+        assert_eq!(solutions, vec![vec![0]]);
+    }
+}
@@ -0,0 +1,44 @@
+//! A Gym-style reinforcement learning environment over the N-queens board.
+//!
+//! This turns the existing `objective`/neighbour machinery from
+//! [`Board`](crate::board::Board) into a step-by-step decision process: an
+//! agent repeatedly moves one queen at a time and is rewarded for reducing
+//! the number of endangered pairs, without having to reimplement the board
+//! dynamics.
+
+/// The observable state of an [`Environment`]: the current per-column row
+/// placement, plus how many pairs of queens currently endanger each other.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Observation {
+    /// `rows[col]` is the row of the queen placed on column `col`.
+    pub rows: Vec<usize>,
+    /// Number of pairs of queens currently endangering each other.
+    pub num_conflicts: usize,
+}
+
+/// An action available to an agent: move the queen on `column` to `row`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Action {
+    pub column: usize,
+    pub row: usize,
+}
+
+/// The result of taking a step in an [`Environment`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Step {
+    pub observation: Observation,
+    pub reward: f32,
+    pub done: bool,
+}
+
+/// A Gym-style environment: reset to an initial episode, then repeatedly
+/// step with an [`Action`] to get a new [`Observation`] and reward.
+pub trait Environment {
+    /// Resets the environment to a fresh, randomly placed episode and
+    /// returns its initial observation.
+    fn reset(&mut self) -> Observation;
+
+    /// Applies `action`, returning the resulting observation, reward, and
+    /// whether the episode has ended.
+    fn step(&mut self, action: Action) -> Step;
+}
@@ -0,0 +1,6 @@
+//! N-queens solver library.
+
+pub mod board;
+mod dlx;
+pub mod env;
+mod utils;
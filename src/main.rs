@@ -1,5 +1,8 @@
 use clap::Clap;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
 use std::iter::FromIterator;
+use std::time::Duration;
 
 use nqueens::board::Board;
 
@@ -15,6 +18,21 @@ struct Opts {
     /// Initial temperature (default is 1000)
     #[clap(short, long)]
     temperature: Option<f32>,
+    /// Seed for the random number generator, for reproducible runs.
+    #[clap(short, long)]
+    seed: Option<u64>,
+    /// Algorithm to use: "annealing" (default) or "min-conflicts".
+    #[clap(short, long, default_value = "annealing")]
+    algorithm: String,
+    /// Run with a wall-clock time budget (in milliseconds) instead of a
+    /// fixed iteration count, using geometric cooling and automatic
+    /// reheating. Takes the place of the `iterations` argument and of
+    /// `--algorithm`.
+    #[clap(long)]
+    time_limit_ms: Option<u64>,
+    /// Geometric cooling rate used by `--time-limit-ms` (default is 0.9999).
+    #[clap(long)]
+    alpha: Option<f32>,
 }
 
 fn main() {
@@ -36,8 +54,30 @@ fn main() {
         return;
     }
 
+    let mut rng = match options.seed {
+        Some(seed) => ChaCha8Rng::seed_from_u64(seed),
+        None => ChaCha8Rng::from_entropy(),
+    };
+
     let board = Board::new(options.n);
-    let solution = board.simulated_annealing(initial_temperature, options.iterations);
+    let solution = match options.time_limit_ms {
+        Some(time_limit_ms) => board.simulated_annealing_timed(
+            initial_temperature,
+            options.alpha.unwrap_or(0.9999),
+            Duration::from_millis(time_limit_ms),
+            &mut rng,
+        ),
+        None => match options.algorithm.as_str() {
+            "min-conflicts" => board.min_conflicts(options.iterations, &mut rng),
+            "annealing" => {
+                board.simulated_annealing(initial_temperature, options.iterations, &mut rng)
+            }
+            _ => {
+                println!("Please specify a valid algorithm: \"annealing\" or \"min-conflicts\".");
+                return;
+            }
+        },
+    };
 
     let mut i = 0;
     for y in 0..options.n {
@@ -0,0 +1,12 @@
+//! Small numeric helpers shared across the crate.
+
+/// Returns the absolute difference between two `usize` values.
+#[inline]
+#[must_use]
+pub fn unsigned_diff(a: usize, b: usize) -> usize {
+    if a > b {
+        a - b
+    } else {
+        b - a
+    }
+}